@@ -0,0 +1,322 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use game::{AnyGameEvent, GameEvent, PieceIndex, PieceMovedEvent, PlayerCursorMovedEvent, PlayerId};
+
+const POSITION_QUANTUM: f32 = 1.0 / 16.0;
+
+const TAG_PIECE_MOVED: u8 = 0;
+const TAG_PLAYER_CURSOR_MOVED: u8 = 1;
+const TAG_JSON: u8 = 2;
+
+const POS_DELTA: u8 = 0;
+const POS_FULL: u8 = 1;
+
+/// Keyed alongside real piece indices; no puzzle has anywhere near
+/// `usize::MAX` rows, so this can never collide with one.
+const CURSOR_SENTINEL_ROW: usize = usize::MAX;
+
+fn cursor_key(player_id: PlayerId) -> PieceIndex {
+    PieceIndex(CURSOR_SENTINEL_ROW, player_id.0 as usize)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn try_read_varint(bytes: &[u8], cursor: &mut usize) -> Option<u32> {
+    let mut value = 0u32;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(*cursor)?;
+        *cursor += 1;
+        value |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+        if shift >= 32 {
+            return None;
+        }
+    }
+}
+
+fn write_index(out: &mut Vec<u8>, index: PieceIndex) {
+    write_varint(out, index.0 as u32);
+    write_varint(out, index.1 as u32);
+}
+
+fn try_read_index(bytes: &[u8], cursor: &mut usize) -> Option<PieceIndex> {
+    let row = try_read_varint(bytes, cursor)? as usize;
+    let col = try_read_varint(bytes, cursor)? as usize;
+    Some(PieceIndex(row, col))
+}
+
+fn encode_position(out: &mut Vec<u8>, cache: &HashMap<PieceIndex, Vec2>, key: PieceIndex, pos: Vec2) {
+    let delta = cache.get(&key).map(|&last| (pos - last) / POSITION_QUANTUM);
+    match delta {
+        Some(delta) if delta.x.abs() <= i16::MAX as f32 && delta.y.abs() <= i16::MAX as f32 => {
+            out.push(POS_DELTA);
+            out.extend_from_slice(&(delta.x.round() as i16).to_le_bytes());
+            out.extend_from_slice(&(delta.y.round() as i16).to_le_bytes());
+        }
+        _ => {
+            out.push(POS_FULL);
+            out.extend_from_slice(&pos.x.to_le_bytes());
+            out.extend_from_slice(&pos.y.to_le_bytes());
+        }
+    }
+}
+
+fn try_decode_position(
+    bytes: &[u8],
+    cursor: &mut usize,
+    cache: &mut HashMap<PieceIndex, Vec2>,
+    key: PieceIndex,
+) -> Option<Vec2> {
+    let mode = *bytes.get(*cursor)?;
+    *cursor += 1;
+    let pos = match mode {
+        POS_FULL => {
+            let x = f32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+            let y = f32::from_le_bytes(bytes.get(*cursor + 4..*cursor + 8)?.try_into().ok()?);
+            *cursor += 8;
+            Vec2::new(x, y)
+        }
+        POS_DELTA => {
+            let dx = i16::from_le_bytes(bytes.get(*cursor..*cursor + 2)?.try_into().ok()?);
+            let dy = i16::from_le_bytes(bytes.get(*cursor + 2..*cursor + 4)?.try_into().ok()?);
+            *cursor += 4;
+            cache.get(&key).copied().unwrap_or(Vec2::ZERO) + Vec2::new(dx as f32, dy as f32) * POSITION_QUANTUM
+        }
+        _ => return None,
+    };
+    cache.insert(key, pos);
+    Some(pos)
+}
+
+#[derive(Default)]
+pub struct EventCodec {
+    sent_positions: HashMap<PieceIndex, Vec2>,
+    received_positions: HashMap<PieceIndex, Vec2>,
+}
+
+impl EventCodec {
+    pub fn encode_frame(&self, events: &[AnyGameEvent]) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_varint(&mut out, events.len() as u32);
+        for event in events {
+            self.encode_event(&mut out, event);
+        }
+        out
+    }
+
+    fn encode_event(&self, out: &mut Vec<u8>, event: &AnyGameEvent) {
+        match event {
+            AnyGameEvent::PieceMoved(e) => {
+                out.push(TAG_PIECE_MOVED);
+                write_index(out, e.index);
+                encode_position(out, &self.sent_positions, e.index, Vec2::new(e.x, e.y));
+            }
+            AnyGameEvent::PlayerCursorMoved(e) => {
+                out.push(TAG_PLAYER_CURSOR_MOVED);
+                let key = cursor_key(e.player_id);
+                write_index(out, key);
+                encode_position(out, &self.sent_positions, key, Vec2::new(e.x, e.y));
+            }
+            other => {
+                out.push(TAG_JSON);
+                let json = other.serialize();
+                write_varint(out, json.len() as u32);
+                out.extend_from_slice(json.as_bytes());
+            }
+        }
+    }
+
+    pub fn commit_sent(&mut self, events: &[AnyGameEvent]) {
+        for event in events {
+            match event {
+                AnyGameEvent::PieceMoved(e) => {
+                    self.sent_positions.insert(e.index, Vec2::new(e.x, e.y));
+                }
+                AnyGameEvent::PlayerCursorMoved(e) => {
+                    self.sent_positions
+                        .insert(cursor_key(e.player_id), Vec2::new(e.x, e.y));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn decode_frame(&mut self, bytes: &[u8]) -> Vec<AnyGameEvent> {
+        let mut cursor = 0;
+        let Some(count) = try_read_varint(bytes, &mut cursor) else {
+            warn!("Dropping binary frame with no readable event count");
+            return Vec::new();
+        };
+        let mut events = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            match self.decode_event(bytes, &mut cursor) {
+                Some(event) => events.push(event),
+                None => {
+                    warn!("Dropping truncated or malformed binary frame after {} of {count} events", events.len());
+                    break;
+                }
+            }
+        }
+        events
+    }
+
+    fn decode_event(&mut self, bytes: &[u8], cursor: &mut usize) -> Option<AnyGameEvent> {
+        let tag = *bytes.get(*cursor)?;
+        *cursor += 1;
+        match tag {
+            TAG_PIECE_MOVED => {
+                let index = try_read_index(bytes, cursor)?;
+                let pos = try_decode_position(bytes, cursor, &mut self.received_positions, index)?;
+                Some(AnyGameEvent::PieceMoved(PieceMovedEvent {
+                    index,
+                    x: pos.x,
+                    y: pos.y,
+                }))
+            }
+            TAG_PLAYER_CURSOR_MOVED => {
+                let key = try_read_index(bytes, cursor)?;
+                let pos = try_decode_position(bytes, cursor, &mut self.received_positions, key)?;
+                Some(AnyGameEvent::PlayerCursorMoved(PlayerCursorMovedEvent {
+                    player_id: PlayerId(key.1 as u32),
+                    x: pos.x,
+                    y: pos.y,
+                }))
+            }
+            TAG_JSON => {
+                let len = try_read_varint(bytes, cursor)? as usize;
+                let end = cursor.checked_add(len)?;
+                let json = std::str::from_utf8(bytes.get(*cursor..end)?).ok()?;
+                let event = AnyGameEvent::deserialize(json).ok();
+                *cursor = end;
+                event
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::PieceConnectionEvent;
+
+    #[test]
+    fn round_trips_delta_encoded_and_json_fallback_events() {
+        let events = vec![
+            AnyGameEvent::PieceMoved(PieceMovedEvent {
+                index: PieceIndex(1, 2),
+                x: 10.0,
+                y: 20.0,
+            }),
+            AnyGameEvent::PlayerCursorMoved(PlayerCursorMovedEvent {
+                player_id: PlayerId(7),
+                x: 5.0,
+                y: 6.0,
+            }),
+            AnyGameEvent::PieceConnection(PieceConnectionEvent {
+                index: PieceIndex(3, 4),
+            }),
+        ];
+
+        let mut sender = EventCodec::default();
+        let frame = sender.encode_frame(&events);
+        sender.commit_sent(&events);
+
+        let mut receiver = EventCodec::default();
+        let decoded = receiver.decode_frame(&frame);
+        assert_eq!(decoded.len(), events.len());
+
+        match &decoded[0] {
+            AnyGameEvent::PieceMoved(e) => {
+                let PieceIndex(row, col) = e.index;
+                assert_eq!((row, col), (1, 2));
+                assert_eq!((e.x, e.y), (10.0, 20.0));
+            }
+            other => panic!("expected PieceMoved, got {other:?}"),
+        }
+        match &decoded[1] {
+            AnyGameEvent::PlayerCursorMoved(e) => {
+                let PlayerId(id) = e.player_id;
+                assert_eq!(id, 7);
+                assert_eq!((e.x, e.y), (5.0, 6.0));
+            }
+            other => panic!("expected PlayerCursorMoved, got {other:?}"),
+        }
+        match &decoded[2] {
+            AnyGameEvent::PieceConnection(e) => {
+                let PieceIndex(row, col) = e.index;
+                assert_eq!((row, col), (3, 4));
+            }
+            other => panic!("expected PieceConnection, got {other:?}"),
+        }
+
+        // A second frame for the same piece goes out as a delta against the
+        // position committed above.
+        let moved = vec![AnyGameEvent::PieceMoved(PieceMovedEvent {
+            index: PieceIndex(1, 2),
+            x: 10.5,
+            y: 19.25,
+        })];
+        let frame = sender.encode_frame(&moved);
+        sender.commit_sent(&moved);
+        // count, tag, row, col, then the position mode byte
+        assert_eq!(frame[4], POS_DELTA);
+        match &receiver.decode_frame(&frame)[..] {
+            [AnyGameEvent::PieceMoved(e)] => assert_eq!((e.x, e.y), (10.5, 19.25)),
+            other => panic!("expected one PieceMoved, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn delta_encoding_falls_back_to_full_precision_past_i16_range() {
+        let key = PieceIndex(0, 0);
+        let mut cache = HashMap::new();
+        cache.insert(key, Vec2::ZERO);
+
+        let mut within_range = Vec::new();
+        encode_position(
+            &mut within_range,
+            &cache,
+            key,
+            Vec2::new(i16::MAX as f32 * POSITION_QUANTUM, 0.0),
+        );
+        assert_eq!(within_range[0], POS_DELTA);
+
+        let mut past_range = Vec::new();
+        encode_position(
+            &mut past_range,
+            &cache,
+            key,
+            Vec2::new((i16::MAX as f32 + 1.0) * POSITION_QUANTUM, 0.0),
+        );
+        assert_eq!(past_range[0], POS_FULL);
+    }
+
+    #[test]
+    fn decode_frame_drops_truncated_or_malformed_data_instead_of_panicking() {
+        let mut codec = EventCodec::default();
+
+        // Claims 5 events but the buffer ends right after the count.
+        assert!(codec.decode_frame(&[5]).is_empty());
+
+        // A TAG_JSON event whose declared length runs past the end of the
+        // buffer — exactly the case that used to overflow `cursor + len`.
+        let mut garbage = vec![1u8, TAG_JSON];
+        write_varint(&mut garbage, u32::MAX);
+        assert!(codec.decode_frame(&garbage).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use game::{PlayerCursorMovedEvent, PlayerDisconnectedEvent, PlayerId};
+
+use crate::pieces::MAX_PIECE_HEIGHT;
+use crate::states::AppState;
+
+/// Per-second rate at which a cursor closes in on its latest reported
+/// position: each frame it covers `CURSOR_LERP_SPEED * delta_seconds` (capped
+/// at 1) of the remaining distance. Tuned so a cursor visibly
+/// glides rather than snapping between the network's sparser updates.
+const CURSOR_LERP_SPEED: f32 = 12.0;
+
+const CURSOR_SIZE: f32 = 24.0;
+const CURSOR_LABEL_OFFSET: f32 = 24.0;
+
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Players::default())
+            .add_systems(OnEnter(AppState::Cutting), reset_players)
+            .add_systems(OnEnter(AppState::Playing), spawn_player_count_ui)
+            .add_systems(OnExit(AppState::Playing), despawn_player_count_ui)
+            .add_systems(
+                Update,
+                (
+                    spawn_or_retarget_cursors,
+                    despawn_cursors,
+                    move_cursors_towards_target,
+                    update_player_count_ui,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Players(HashMap<PlayerId, Entity>);
+
+impl Players {
+    pub fn count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Component)]
+struct RemoteCursor {
+    target: Vec2,
+}
+
+fn reset_players(mut commands: Commands, mut players: ResMut<Players>) {
+    for (_, entity) in players.0.drain() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn spawn_or_retarget_cursors(
+    mut commands: Commands,
+    mut cursor_moved_events: EventReader<PlayerCursorMovedEvent>,
+    mut players: ResMut<Players>,
+    mut cursor_query: Query<&mut RemoteCursor>,
+) {
+    for event in cursor_moved_events.iter() {
+        let target = Vec2::new(event.x, event.y);
+        if let Some(&entity) = players.0.get(&event.player_id) {
+            cursor_query.get_mut(entity).unwrap().target = target;
+        } else {
+            let entity = spawn_cursor(&mut commands, event.player_id, target);
+            players.0.insert(event.player_id, entity);
+        }
+    }
+}
+
+fn spawn_cursor(commands: &mut Commands, player_id: PlayerId, target: Vec2) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: cursor_color(player_id),
+                    custom_size: Some(Vec2::splat(CURSOR_SIZE)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(target.x, target.y, MAX_PIECE_HEIGHT + 1.0),
+                ..Default::default()
+            },
+            RemoteCursor { target },
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(format!("Player {}", player_id.0), TextStyle::default()),
+                transform: Transform::from_xyz(0.0, CURSOR_LABEL_OFFSET, 0.0),
+                ..Default::default()
+            });
+        })
+        .id()
+}
+
+fn despawn_cursors(
+    mut commands: Commands,
+    mut disconnected_events: EventReader<PlayerDisconnectedEvent>,
+    mut players: ResMut<Players>,
+) {
+    for event in disconnected_events.iter() {
+        if let Some(entity) = players.0.remove(&event.player_id) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn move_cursors_towards_target(mut cursors: Query<(&RemoteCursor, &mut Transform)>, time: Res<Time>) {
+    let t = (CURSOR_LERP_SPEED * time.delta_seconds()).min(1.0);
+    for (cursor, mut transform) in &mut cursors {
+        let current = transform.translation.truncate();
+        let next = current.lerp(cursor.target, t);
+        transform.translation.x = next.x;
+        transform.translation.y = next.y;
+    }
+}
+
+/// Picks a stable, visually distinct color per player ID so returning players
+/// keep the same cursor color for the life of the connection.
+fn cursor_color(player_id: PlayerId) -> Color {
+    let hue = (player_id.0 as f32 * 47.0) % 360.0;
+    Color::hsl(hue, 0.65, 0.55)
+}
+
+#[derive(Component)]
+struct PlayerCountText;
+
+fn spawn_player_count_ui(mut commands: Commands) {
+    commands.spawn((
+        PlayerCountText,
+        TextBundle::from_section("", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            right: Val::Px(8.0),
+            ..Default::default()
+        }),
+    ));
+}
+
+fn despawn_player_count_ui(mut commands: Commands, ui_root: Query<Entity, With<PlayerCountText>>) {
+    for entity in &ui_root {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update_player_count_ui(players: Res<Players>, mut text: Query<&mut Text, With<PlayerCountText>>) {
+    let Ok(mut text) = text.get_single_mut() else {
+        return;
+    };
+    let count = players.count();
+    text.sections[0].value = format!("{count} player{} solving", if count == 1 { "" } else { "s" });
+}
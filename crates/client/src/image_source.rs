@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::states::AppState;
+
+const DEFAULT_PIECE_COUNT: u32 = 150;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSource {
+    WikimediaPictureOfTheDay,
+    WikimediaFeatured,
+}
+
+impl ImageSource {
+    pub const ALL: [ImageSource; 2] = [
+        ImageSource::WikimediaPictureOfTheDay,
+        ImageSource::WikimediaFeatured,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImageSource::WikimediaPictureOfTheDay => "Wikimedia picture of the day",
+            ImageSource::WikimediaFeatured => "Wikimedia featured image",
+        }
+    }
+}
+
+/// Absent means "use whatever the server would send by default".
+#[derive(Resource, Clone, Copy)]
+pub struct SelectedImageSource {
+    pub source: ImageSource,
+    pub piece_count: u32,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ImageSourceRequest {
+    pub source: ImageSource,
+    pub piece_count: u32,
+}
+
+pub struct ImageSourcePickerPlugin;
+
+impl Plugin for ImageSourcePickerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::ImageSourcePicker), spawn_picker_ui)
+            .add_systems(OnExit(AppState::ImageSourcePicker), despawn_picker_ui)
+            .add_systems(
+                Update,
+                handle_source_buttons.run_if(in_state(AppState::ImageSourcePicker)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct ImageSourcePickerUi;
+
+#[derive(Component)]
+struct ImageSourceButton(ImageSource);
+
+const BUTTON_BACKGROUND: Color = Color::rgb(0.2, 0.2, 0.2);
+const BUTTON_HOVERED_BACKGROUND: Color = Color::rgb(0.3, 0.3, 0.3);
+
+fn spawn_picker_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            ImageSourcePickerUi,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            for source in ImageSource::ALL {
+                parent
+                    .spawn((
+                        ImageSourceButton(source),
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(12.0)),
+                                ..Default::default()
+                            },
+                            background_color: BUTTON_BACKGROUND.into(),
+                            ..Default::default()
+                        },
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            source.label(),
+                            TextStyle::default(),
+                        ));
+                    });
+            }
+        });
+}
+
+fn despawn_picker_ui(mut commands: Commands, ui_root: Query<Entity, With<ImageSourcePickerUi>>) {
+    for entity in &ui_root {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn handle_source_buttons(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    interactions: Query<(&Interaction, &ImageSourceButton), Changed<Interaction>>,
+    mut backgrounds: Query<(&Interaction, &mut BackgroundColor), With<ImageSourceButton>>,
+) {
+    for (interaction, mut background) in &mut backgrounds {
+        *background = match interaction {
+            Interaction::Hovered => BUTTON_HOVERED_BACKGROUND.into(),
+            Interaction::Pressed | Interaction::None => BUTTON_BACKGROUND.into(),
+        };
+    }
+
+    for (interaction, button) in &interactions {
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(SelectedImageSource {
+                source: button.0,
+                piece_count: DEFAULT_PIECE_COUNT,
+            });
+            next_state.set(AppState::Connecting);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+mod better_quad;
+mod codec;
+mod cursors;
+mod image_source;
+mod material;
+mod menu;
+mod network;
+mod offline;
+mod pieces;
+mod states;
+mod ui;
+mod worker;
+
+use cursors::CursorPlugin;
+use image_source::ImageSourcePickerPlugin;
+use menu::MainMenuPlugin;
+use network::NetworkPlugin;
+use offline::OfflinePlugin;
+use pieces::PiecePlugin;
+use states::AppState;
+use ui::UiPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .init_state::<AppState>()
+        .add_plugins((
+            UiPlugin,
+            MainMenuPlugin,
+            NetworkPlugin,
+            PiecePlugin,
+            CursorPlugin,
+            OfflinePlugin,
+            ImageSourcePickerPlugin,
+        ))
+        .run();
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::offline::start_offline_session;
+use crate::states::AppState;
+
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::MainMenu), spawn_menu_ui)
+            .add_systems(OnExit(AppState::MainMenu), despawn_menu_ui)
+            .add_systems(
+                Update,
+                handle_menu_buttons.run_if(in_state(AppState::MainMenu)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct MainMenuUi;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum MenuButton {
+    PlayOnline,
+    PlayOffline,
+    ChooseImageSource,
+}
+
+const BUTTON_BACKGROUND: Color = Color::rgb(0.2, 0.2, 0.2);
+const BUTTON_HOVERED_BACKGROUND: Color = Color::rgb(0.3, 0.3, 0.3);
+
+fn spawn_menu_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            MainMenuUi,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            for (button, label) in [
+                (MenuButton::PlayOnline, "Join a puzzle room"),
+                (MenuButton::PlayOffline, "Play offline"),
+                (MenuButton::ChooseImageSource, "Choose an image source"),
+            ] {
+                parent
+                    .spawn((
+                        button,
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(12.0)),
+                                ..Default::default()
+                            },
+                            background_color: BUTTON_BACKGROUND.into(),
+                            ..Default::default()
+                        },
+                    ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(label, TextStyle::default()));
+                    });
+            }
+        });
+}
+
+fn despawn_menu_ui(mut commands: Commands, ui_root: Query<Entity, With<MainMenuUi>>) {
+    for entity in &ui_root {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn handle_menu_buttons(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    interactions: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut backgrounds: Query<(&Interaction, &mut BackgroundColor), With<MenuButton>>,
+) {
+    for (interaction, mut background) in &mut backgrounds {
+        *background = match interaction {
+            Interaction::Hovered => BUTTON_HOVERED_BACKGROUND.into(),
+            Interaction::Pressed | Interaction::None => BUTTON_BACKGROUND.into(),
+        };
+    }
+
+    let pressed = interactions
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| *button);
+
+    match pressed {
+        Some(MenuButton::PlayOnline) => next_state.set(AppState::Connecting),
+        Some(MenuButton::PlayOffline) => {
+            start_offline_session(commands);
+            next_state.set(AppState::LoadingOffline);
+        }
+        Some(MenuButton::ChooseImageSource) => next_state.set(AppState::ImageSourcePicker),
+        None => {}
+    }
+}
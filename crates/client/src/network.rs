@@ -5,39 +5,167 @@ use bevy::tasks::AsyncComputeTaskPool;
 use futures_util::future::join;
 use futures_util::{select, FutureExt, SinkExt, StreamExt};
 use game::{
-    AnyGameEvent, GameEvent, PieceConnectionCheckEvent, PieceConnectionEvent, PieceMovedEvent,
-    PiecePickedUpEvent, PiecePutDownEvent, PlayerCursorMovedEvent, PlayerDisconnectedEvent, Puzzle,
+    AnyGameEvent, GameEvent, PieceConnectionCheckEvent, PieceConnectionEvent, PieceIndex,
+    PieceMovedEvent, PiecePickedUpEvent, PiecePutDownEvent, PlayerCursorMovedEvent,
+    PlayerDisconnectedEvent, Puzzle,
 };
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::oneshot;
 use ws_stream_wasm::{WsMessage, WsMeta};
 
+use crate::codec::EventCodec;
+use crate::image_source::{ImageSourceRequest, SelectedImageSource};
 use crate::states::AppState;
 use crate::ui::LoadingMessage;
 use crate::worker::Worker;
 
+/// Length of a shareable room ID, as assigned by the server.
+const ROOM_ID_LEN: usize = 7;
+
+/// Alphanumeric charset with easily-confused characters (`0`/`O`, `1`/`l`/`I`) removed,
+/// so a room ID can be read aloud or typed in by hand without ambiguity.
+const ROOM_ID_CHARSET: &str = "23456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// The ID of the puzzle room this client is connected to, once the server has
+/// confirmed or assigned one. Present once [`AppState::Cutting`] is reached in
+/// an online session; an offline session never has one.
+#[derive(Resource, Clone)]
+pub struct RoomId(pub String);
+
+/// Whether this connection negotiated the compact binary event protocol.
+/// Inserted alongside [`RoomId`] once the server's handshake reply is known,
+/// so `event_io` can fall back to one-JSON-frame-per-event for a server that
+/// doesn't understand binary frames yet.
+#[derive(Resource, Clone, Copy)]
+struct BinaryProtocol(bool);
+
+/// Sent as the very first message on a fresh WebSocket connection, before the
+/// puzzle-download exchange. Lets the client ask to join a specific room, or
+/// leave `room_id` unset to have the server assign a fresh one, and
+/// advertises that this client can speak the binary event protocol.
+#[derive(Serialize)]
+struct JoinRoom {
+    room_id: Option<String>,
+    binary: bool,
+}
+
+/// The server's reply to [`JoinRoom`], confirming which room the client ended
+/// up in (either the one it asked for, or a freshly assigned one) and
+/// whether binary frames were negotiated for this connection.
+#[derive(Deserialize)]
+struct RoomJoined {
+    room_id: String,
+    #[serde(default)]
+    binary: bool,
+}
+
+/// Reads a room ID out of the page URL (e.g. `/#abc2345`), if one is present
+/// and well-formed.
+fn room_id_from_location(location: &web_sys::Location) -> Option<String> {
+    let hash = location.hash().ok()?;
+    let candidate = hash.trim_start_matches('#');
+    (candidate.len() == ROOM_ID_LEN && candidate.chars().all(|c| ROOM_ID_CHARSET.contains(c)))
+        .then(|| candidate.to_string())
+}
+
+/// Sent once a reconnected socket has rejoined the room, asking the server
+/// for the current position and group membership of every piece so the
+/// client can resync without re-cutting the puzzle.
+#[derive(Serialize)]
+struct SnapshotRequest;
+
+/// The server's reply to [`SnapshotRequest`]: the current state of every
+/// piece, applied locally by driving the same `Puzzle::apply_event` /
+/// `move_piece` path a live `PieceMoved` event would.
+#[derive(Deserialize)]
+struct Snapshot {
+    pieces: Vec<PieceSnapshot>,
+}
+
+#[derive(Deserialize)]
+struct PieceSnapshot {
+    row: usize,
+    col: usize,
+    x: f32,
+    y: f32,
+    /// Mirrors `Puzzle::piece_group_locked`: whether this piece had already
+    /// connected into place, possibly while we were disconnected.
+    group_locked: bool,
+}
+
+/// Events generated locally while disconnected, held here until the socket
+/// reconnects so they can be replayed instead of lost.
+#[derive(Resource, Default)]
+struct PendingEvents(Vec<AnyGameEvent>);
+
 pub struct NetworkPlugin;
 
 impl Plugin for NetworkPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Connecting), spawn_network_io_task)
+        app.insert_resource(PendingEvents::default())
+            .init_resource::<SharedEventReaders>()
+            .add_systems(OnEnter(AppState::Connecting), spawn_network_io_task)
+            .add_systems(OnEnter(AppState::Reconnecting), spawn_reconnect_io_task)
             .add_systems(
                 Update,
                 download_puzzle.run_if(in_state(AppState::Downloading)),
             )
-            .add_systems(Update, event_io.run_if(in_state(AppState::Playing)));
+            .add_systems(
+                Update,
+                resync_after_reconnect.run_if(in_state(AppState::Reconnecting)),
+            )
+            .add_systems(
+                Update,
+                buffer_events_while_reconnecting.run_if(in_state(AppState::Reconnecting)),
+            )
+            .add_systems(
+                Update,
+                event_io
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not(resource_exists::<crate::offline::OfflineMode>())),
+            );
     }
 }
 
-type NetworkIO = Worker<String, String>;
+/// A message crossing the boundary between the ECS world and the background
+/// network task, in either direction: plain JSON text (puzzle payloads, the
+/// room handshake, and events on a connection that hasn't negotiated binary)
+/// or a coalesced binary event frame (see `codec`).
+enum Frame {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+type NetworkIO = Worker<Frame, Frame>;
 
 fn spawn_network_io_task(
     mut commands: Commands,
     mut next_state: ResMut<NextState<AppState>>,
     mut loading_msg: ResMut<LoadingMessage>,
 ) {
+    spawn_io_task(&mut commands, None);
+    next_state.set(AppState::Downloading);
+    loading_msg.0 = String::from("Connecting to server");
+}
+
+/// Re-opens the socket after a disconnect, asking to rejoin the room we were
+/// already in rather than landing in a new one.
+fn spawn_reconnect_io_task(
+    mut commands: Commands,
+    room_id: Res<RoomId>,
+    mut loading_msg: ResMut<LoadingMessage>,
+) {
+    spawn_io_task(&mut commands, Some(room_id.0.clone()));
+    loading_msg.0 = String::from("Reconnecting\u{2026}");
+}
+
+/// Opens the WebSocket and runs the join handshake. `rejoin_room_id` pins the
+/// room to ask for on reconnect; a fresh connection instead falls back to
+/// whatever room ID (if any) is in the page URL.
+fn spawn_io_task(commands: &mut Commands, rejoin_room_id: Option<String>) {
     let thread_pool = AsyncComputeTaskPool::get();
-    let io = NetworkIO::spawn(thread_pool, |mut client_rx, client_tx| async move {
+    let io = NetworkIO::spawn(thread_pool, move |mut client_rx, client_tx| async move {
         let window = web_sys::window().unwrap();
         let document = window.document().unwrap();
         let location = document.location().unwrap();
@@ -57,6 +185,36 @@ fn spawn_network_io_task(
         };
 
         let (mut ws_tx, mut ws_rx) = ws_io.split();
+
+        let join_room = JoinRoom {
+            room_id: rejoin_room_id.or_else(|| room_id_from_location(&location)),
+            binary: true,
+        };
+        if ws_tx
+            .send(WsMessage::Text(serde_json::to_string(&join_room).unwrap()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        // The handshake reply is parsed here (rather than left to the ECS
+        // side, as with the puzzle payload) because the negotiated protocol
+        // has to be known before the raw receive/send loops below start. The
+        // raw text is still forwarded on unchanged so `download_puzzle` can
+        // pick the room ID out of it exactly as before.
+        let ack = match ws_rx.next().await {
+            Some(WsMessage::Text(msg)) => msg,
+            _ => return,
+        };
+        let use_binary = match serde_json::from_str::<RoomJoined>(&ack) {
+            Ok(joined) => joined.binary,
+            Err(_) => return,
+        };
+        if client_tx.send(Frame::Text(ack)).is_err() {
+            return;
+        }
+
         let (dc_tx, dc_rx) = oneshot::channel();
 
         let net_rx_handler = async move {
@@ -69,8 +227,8 @@ fn spawn_network_io_task(
                     res = ws_rx.next().fuse() => match res {
                         None => break,
                         Some(msg) => match msg {
-                            WsMessage::Text(msg) => client_tx.send(msg).unwrap(),
-                            WsMessage::Binary(msg) => warn!("Strange message received from server: {msg:#?}"),
+                            WsMessage::Text(msg) => client_tx.send(Frame::Text(msg)).unwrap(),
+                            WsMessage::Binary(bytes) => client_tx.send(Frame::Binary(bytes)).unwrap(),
                         }
                     },
                 }
@@ -78,8 +236,16 @@ fn spawn_network_io_task(
         };
 
         let net_tx_handler = async move {
-            while let Some(msg) = client_rx.recv().await {
-                if ws_tx.send(WsMessage::Text(msg)).await.is_err() {
+            while let Some(frame) = client_rx.recv().await {
+                let msg = match frame {
+                    Frame::Text(text) => WsMessage::Text(text),
+                    Frame::Binary(bytes) if use_binary => WsMessage::Binary(bytes),
+                    Frame::Binary(_) => {
+                        warn!("Dropping binary frame on a connection that didn't negotiate binary");
+                        continue;
+                    }
+                };
+                if ws_tx.send(msg).await.is_err() {
                     break;
                 }
             }
@@ -89,17 +255,44 @@ fn spawn_network_io_task(
         join(net_rx_handler, net_tx_handler).await;
     });
     commands.insert_resource(io);
-    next_state.set(AppState::Downloading);
-    loading_msg.0 = String::from("Connecting to server");
 }
 
 fn download_puzzle(
     mut commands: Commands,
     mut network_io: ResMut<NetworkIO>,
     mut next_state: ResMut<NextState<AppState>>,
+    mut loading_msg: ResMut<LoadingMessage>,
+    room_id: Option<Res<RoomId>>,
+    selected_image_source: Option<Res<SelectedImageSource>>,
 ) {
     match network_io.output.try_recv() {
-        Ok(msg) => {
+        Ok(Frame::Text(msg)) => {
+            if room_id.is_none() {
+                match serde_json::from_str::<RoomJoined>(msg.as_str()) {
+                    Ok(joined) => {
+                        loading_msg.0 =
+                            format!("Downloading puzzle (share this room: #{})", joined.room_id);
+                        commands.insert_resource(BinaryProtocol(joined.binary));
+                        commands.insert_resource(EventCodec::default());
+                        commands.insert_resource(RoomId(joined.room_id));
+
+                        if let Some(selected) = selected_image_source {
+                            let request = ImageSourceRequest {
+                                source: selected.source,
+                                piece_count: selected.piece_count,
+                            };
+                            let _ = network_io
+                                .input
+                                .send(Frame::Text(serde_json::to_string(&request).unwrap()));
+                        }
+                    }
+                    Err(_) => {
+                        warn!("Unexpected message from server while waiting for room assignment: {msg:#?}");
+                    }
+                }
+                return;
+            }
+
             if let Ok(puzzle) = Puzzle::deserialize(msg.as_str()) {
                 commands.insert_resource(puzzle);
                 next_state.set(AppState::Cutting);
@@ -107,6 +300,9 @@ fn download_puzzle(
                 warn!("Unexpected message from server while waiting for puzzle: {msg:#?}");
             }
         }
+        Ok(Frame::Binary(bytes)) => {
+            warn!("Unexpected binary message from server while downloading puzzle: {bytes:#?}");
+        }
         Err(e) => match e {
             TryRecvError::Empty => (),
             TryRecvError::Disconnected => next_state.set(AppState::Connecting),
@@ -114,65 +310,196 @@ fn download_puzzle(
     }
 }
 
+/// Where we are in the reconnect handshake. Tracked explicitly (rather than
+/// a bare `bool`) so a second disconnect mid-resync can reset it back to
+/// [`ReconnectPhase::AwaitingAck`] instead of leaving it stuck expecting a
+/// `Snapshot` that a fresh `RoomJoined` ack will never satisfy.
+#[derive(Default, PartialEq, Eq)]
+enum ReconnectPhase {
+    #[default]
+    AwaitingAck,
+    AwaitingSnapshot,
+}
+
+/// Drives the reconnect handshake: once the freshly reopened socket confirms
+/// which room we rejoined, asks for a snapshot of every piece's current
+/// state, applies it to the already-spawned entities, replays whatever the
+/// player did while disconnected, and returns to `Playing` — no re-cut.
+fn resync_after_reconnect(
+    mut commands: Commands,
+    mut params: EventIoParams,
+    mut network_io: ResMut<NetworkIO>,
+    mut puzzle: ResMut<Puzzle>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut pending: ResMut<PendingEvents>,
+    mut phase: Local<ReconnectPhase>,
+    mut codec: ResMut<EventCodec>,
+    mut binary_protocol: ResMut<BinaryProtocol>,
+    room_id: Res<RoomId>,
+) {
+    match network_io.output.try_recv() {
+        Ok(Frame::Text(msg)) => {
+            if *phase == ReconnectPhase::AwaitingAck {
+                match serde_json::from_str::<RoomJoined>(msg.as_str()) {
+                    Ok(joined) => {
+                        // The reopened socket is a fresh connection: both ends
+                        // start the delta-quantized codec over, so our cache
+                        // has to as well, and the new connection may not have
+                        // renegotiated binary the same way the first one did.
+                        *binary_protocol = BinaryProtocol(joined.binary);
+                        *codec = EventCodec::default();
+
+                        let request = serde_json::to_string(&SnapshotRequest).unwrap();
+                        let _ = network_io.input.send(Frame::Text(request));
+                        *phase = ReconnectPhase::AwaitingSnapshot;
+                    }
+                    Err(_) => {
+                        warn!("Unexpected message from server while rejoining room: {msg:#?}");
+                    }
+                }
+                return;
+            }
+
+            match serde_json::from_str::<Snapshot>(msg.as_str()) {
+                Ok(snapshot) => {
+                    let mut new_events = Vec::new();
+                    for piece in snapshot.pieces {
+                        let index = PieceIndex(piece.row, piece.col);
+                        new_events.extend(puzzle.apply_event(AnyGameEvent::PieceMoved(PieceMovedEvent {
+                            index,
+                            x: piece.x,
+                            y: piece.y,
+                        })));
+                        if piece.group_locked {
+                            new_events.extend(
+                                puzzle.apply_event(AnyGameEvent::PieceConnection(PieceConnectionEvent { index })),
+                            );
+                        }
+                    }
+                    dispatch_and_clear(&mut params, new_events);
+
+                    let replayed: Vec<AnyGameEvent> = pending.0.drain(..).collect();
+                    if !replayed.is_empty() {
+                        if binary_protocol.0 {
+                            let frame = codec.encode_frame(&replayed);
+                            if network_io.input.send(Frame::Binary(frame)).is_ok() {
+                                codec.commit_sent(&replayed);
+                            }
+                        } else {
+                            for event in &replayed {
+                                let _ = network_io.input.send(Frame::Text(event.serialize()));
+                            }
+                        }
+                    }
+
+                    *phase = ReconnectPhase::AwaitingAck;
+                    next_state.set(AppState::Playing);
+                }
+                Err(_) => warn!("Unexpected message from server while waiting for snapshot: {msg:#?}"),
+            }
+        }
+        Ok(Frame::Binary(bytes)) => {
+            warn!("Unexpected binary message from server while reconnecting: {bytes:#?}");
+        }
+        Err(e) => match e {
+            TryRecvError::Empty => (),
+            TryRecvError::Disconnected => {
+                // The socket dropped again before a snapshot came back. Reopen
+                // it the same way `spawn_reconnect_io_task` would (asking to
+                // rejoin `room_id`) rather than falling back to
+                // `AppState::Connecting`: that would re-enter
+                // `spawn_network_io_task`, which asks for the room in the page
+                // URL (or a fresh one) rather than `room_id`, and leaves the stale `RoomId`/`BinaryProtocol`/`EventCodec`
+                // resources around it, wedging `download_puzzle` into
+                // misreading the new room's ack as a `Puzzle`. Reset the
+                // phase and retry in place instead.
+                *phase = ReconnectPhase::AwaitingAck;
+                spawn_io_task(&mut commands, Some(room_id.0.clone()));
+            }
+        },
+    }
+}
+
+/// While waiting on the snapshot above, client-generated events (e.g. the
+/// player finishing a drag they started before the disconnect) still flow
+/// out of bevy as normal, but there's no live socket to forward them to —
+/// buffer them instead of dropping them.
+fn buffer_events_while_reconnecting(mut params: EventIoParams, mut pending: ResMut<PendingEvents>) {
+    pending.0.extend(collect_outgoing_events(&mut params));
+    dispatch_and_clear(&mut params, Vec::new());
+}
+
+/// One reader per event type, shared (via the `SharedEventReaders` resource)
+/// across every system that builds an [`EventIoParams`] — `event_io`,
+/// `resync_after_reconnect`, `buffer_events_while_reconnecting`, and offline's
+/// `loopback_event_io`. The middle two both run in `Reconnecting`, and a
+/// `Local` reader per system would let one of them re-read events another
+/// had already drained and cleared this same tick.
+#[derive(Resource, Default)]
+pub(crate) struct SharedEventReaders {
+    piece_moved: ManualEventReader<PieceMovedEvent>,
+    piece_picked_up: ManualEventReader<PiecePickedUpEvent>,
+    piece_put_down: ManualEventReader<PiecePutDownEvent>,
+    piece_connection_check: ManualEventReader<PieceConnectionCheckEvent>,
+    piece_connection: ManualEventReader<PieceConnectionEvent>,
+    player_cursor_moved: ManualEventReader<PlayerCursorMovedEvent>,
+    player_disconnected: ManualEventReader<PlayerDisconnectedEvent>,
+}
+
 #[derive(SystemParam)]
-struct EventIoParams<'w, 's> {
+pub(crate) struct EventIoParams<'w> {
     piece_moved_events: ResMut<'w, Events<PieceMovedEvent>>,
-    piece_moved_reader: Local<'s, ManualEventReader<PieceMovedEvent>>,
-
     piece_picked_up_events: ResMut<'w, Events<PiecePickedUpEvent>>,
-    piece_picked_up_reader: Local<'s, ManualEventReader<PiecePickedUpEvent>>,
-
     piece_put_down_events: ResMut<'w, Events<PiecePutDownEvent>>,
-    piece_put_down_reader: Local<'s, ManualEventReader<PiecePutDownEvent>>,
-
     piece_connection_check_events: ResMut<'w, Events<PieceConnectionCheckEvent>>,
-    piece_connection_check_reader: Local<'s, ManualEventReader<PieceConnectionCheckEvent>>,
-
     piece_connection_events: ResMut<'w, Events<PieceConnectionEvent>>,
-    piece_connection_reader: Local<'s, ManualEventReader<PieceConnectionEvent>>,
-
     player_cursor_moved_events: ResMut<'w, Events<PlayerCursorMovedEvent>>,
-    player_cursor_moved_reader: Local<'s, ManualEventReader<PlayerCursorMovedEvent>>,
-
     player_disconnected_events: ResMut<'w, Events<PlayerDisconnectedEvent>>,
-    player_disconnected_reader: Local<'s, ManualEventReader<PlayerDisconnectedEvent>>,
+    readers: ResMut<'w, SharedEventReaders>,
 }
 
-fn event_io(
-    mut params: EventIoParams,
-    mut network_io: ResMut<NetworkIO>,
-    mut puzzle: ResMut<Puzzle>,
-    mut next_state: ResMut<NextState<AppState>>,
-) {
-    // forward all events generated by the client to the server
-
-    macro_rules! forward_events {
-        ($reader: ident, $events: ident) => {
-            for event in params.$reader.iter(&params.$events) {
-                if network_io.input.send(event.serialize()).is_err() {
-                    next_state.set(AppState::Connecting);
-                    return;
-                }
+/// Drains every client-generated event this tick into a flat list of
+/// [`AnyGameEvent`]s, ready to either ship out over the network or, for an
+/// offline session, feed straight back into `Puzzle::apply_event` with no
+/// socket in between.
+pub(crate) fn collect_outgoing_events(params: &mut EventIoParams) -> Vec<AnyGameEvent> {
+    let mut outgoing = Vec::new();
+
+    macro_rules! collect_events {
+        ($reader: ident, $events: ident, $variant: ident) => {
+            for event in params.readers.$reader.iter(&params.$events) {
+                outgoing.push(AnyGameEvent::$variant(event.clone()));
             }
         };
     }
 
-    forward_events!(piece_moved_reader, piece_moved_events);
-    forward_events!(piece_picked_up_reader, piece_picked_up_events);
-    forward_events!(piece_put_down_reader, piece_put_down_events);
-    forward_events!(piece_connection_check_reader, piece_connection_check_events);
-    forward_events!(piece_connection_reader, piece_connection_events);
-    forward_events!(player_cursor_moved_reader, player_cursor_moved_events);
-    forward_events!(player_disconnected_reader, player_disconnected_events);
+    collect_events!(piece_moved, piece_moved_events, PieceMoved);
+    collect_events!(piece_picked_up, piece_picked_up_events, PiecePickedUp);
+    collect_events!(piece_put_down, piece_put_down_events, PiecePutDown);
+    collect_events!(
+        piece_connection_check,
+        piece_connection_check_events,
+        PieceConnectionCheck
+    );
+    collect_events!(piece_connection, piece_connection_events, PieceConnection);
+    collect_events!(
+        player_cursor_moved,
+        player_cursor_moved_events,
+        PlayerCursorMoved
+    );
+    collect_events!(
+        player_disconnected,
+        player_disconnected_events,
+        PlayerDisconnected
+    );
 
-    // receive events from the server and apply them to the local puzzle instance
-    let mut new_events = Vec::new();
-    while let Ok(msg) = network_io.output.try_recv() {
-        let event = AnyGameEvent::deserialize(msg.as_str()).unwrap();
-        new_events.extend(puzzle.apply_event(event));
-    }
+    outgoing
+}
 
-    // dispatch new events out to bevy
+/// Sends the results of applying incoming events back out to bevy, then
+/// clears the readers used by [`collect_outgoing_events`] so this tick's
+/// events aren't forwarded again next frame.
+pub(crate) fn dispatch_and_clear(params: &mut EventIoParams, new_events: Vec<AnyGameEvent>) {
     for event in new_events {
         use AnyGameEvent::*;
         match event {
@@ -186,24 +513,96 @@ fn event_io(
         }
     }
 
-    // consume all the events we just dispatched so we don't forward them back out next frame
-    params.piece_moved_reader.clear(&params.piece_moved_events);
+    params.readers.piece_moved.clear(&params.piece_moved_events);
     params
-        .piece_picked_up_reader
+        .readers
+        .piece_picked_up
         .clear(&params.piece_picked_up_events);
     params
-        .piece_put_down_reader
+        .readers
+        .piece_put_down
         .clear(&params.piece_put_down_events);
     params
-        .piece_connection_check_reader
+        .readers
+        .piece_connection_check
         .clear(&params.piece_connection_check_events);
     params
-        .piece_connection_reader
+        .readers
+        .piece_connection
         .clear(&params.piece_connection_events);
     params
-        .player_cursor_moved_reader
+        .readers
+        .player_cursor_moved
         .clear(&params.player_cursor_moved_events);
     params
-        .player_disconnected_reader
+        .readers
+        .player_disconnected
         .clear(&params.player_disconnected_events);
 }
+
+fn event_io(
+    mut params: EventIoParams,
+    mut network_io: ResMut<NetworkIO>,
+    mut puzzle: ResMut<Puzzle>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut codec: ResMut<EventCodec>,
+    binary_protocol: Res<BinaryProtocol>,
+    mut pending: ResMut<PendingEvents>,
+) {
+    let outgoing = collect_outgoing_events(&mut params);
+
+    // forward them to the server, coalesced into one binary frame per tick
+    // where the connection supports it, or one JSON frame per event for a
+    // server that only understands the legacy text protocol
+    if !outgoing.is_empty() {
+        let unsent = if binary_protocol.0 {
+            // one frame, all-or-nothing: nothing was sent if this fails. Only
+            // advance the codec's sent-position cache once the frame is
+            // confirmed to have actually gone out, so a failed send doesn't
+            // leave it referencing a baseline the peer never received.
+            let frame = codec.encode_frame(&outgoing);
+            if network_io.input.send(Frame::Binary(frame)).is_ok() {
+                codec.commit_sent(&outgoing);
+                None
+            } else {
+                Some(0)
+            }
+        } else {
+            // one frame per event: the send loop can fail partway through, so
+            // only the events from the first failure onward are unsent
+            outgoing
+                .iter()
+                .position(|event| network_io.input.send(Frame::Text(event.serialize())).is_err())
+        };
+        if let Some(first_unsent) = unsent {
+            // the socket just dropped; hold onto what we couldn't send and
+            // pick back up via a snapshot resync instead of re-cutting
+            pending.0.extend(outgoing.into_iter().skip(first_unsent));
+            next_state.set(AppState::Reconnecting);
+            return;
+        }
+    }
+
+    // receive events from the server and apply them to the local puzzle instance
+    let mut new_events = Vec::new();
+    loop {
+        match network_io.output.try_recv() {
+            Ok(frame) => {
+                let events = match frame {
+                    Frame::Text(msg) => vec![AnyGameEvent::deserialize(msg.as_str()).unwrap()],
+                    Frame::Binary(bytes) => codec.decode_frame(&bytes),
+                };
+                for event in events {
+                    new_events.extend(puzzle.apply_event(event));
+                }
+            }
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => {
+                next_state.set(AppState::Reconnecting);
+                break;
+            }
+        }
+    }
+
+    dispatch_and_clear(&mut params, new_events);
+}
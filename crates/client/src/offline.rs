@@ -0,0 +1,293 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use game::Puzzle;
+use image::RgbaImage;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::oneshot;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::network::{collect_outgoing_events, dispatch_and_clear, EventIoParams};
+use crate::states::AppState;
+use crate::ui::LoadingMessage;
+use crate::worker::Worker;
+
+const OFFLINE_NUM_ROWS: usize = 10;
+const OFFLINE_NUM_COLS: usize = 15;
+
+#[derive(Resource)]
+pub struct OfflineMode;
+
+pub struct OfflinePlugin;
+
+impl Plugin for OfflinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::LoadingOffline),
+            (spawn_image_picker_task, spawn_back_to_menu_button),
+        )
+        .add_systems(OnExit(AppState::LoadingOffline), despawn_offline_loading_ui)
+        .add_systems(
+            Update,
+            receive_offline_puzzle
+                .run_if(in_state(AppState::LoadingOffline))
+                .run_if(resource_exists::<ImagePicker>()),
+        )
+        .add_systems(
+            Update,
+            handle_retry_button.run_if(in_state(AppState::LoadingOffline)),
+        )
+        .add_systems(
+            Update,
+            handle_back_to_menu_button.run_if(in_state(AppState::LoadingOffline)),
+        )
+        .add_systems(
+            Update,
+            loopback_event_io
+                .run_if(in_state(AppState::Playing))
+                .run_if(resource_exists::<OfflineMode>()),
+        );
+    }
+}
+
+/// Callers should follow this up by setting `AppState::LoadingOffline`.
+pub fn start_offline_session(mut commands: Commands) {
+    commands.insert_resource(OfflineMode);
+}
+
+type ImagePicker = Worker<(), RgbaImage>;
+
+/// Tags every UI entity spawned while in `AppState::LoadingOffline`, so
+/// `despawn_offline_loading_ui` can clean all of it up regardless of which
+/// path (picked an image, backed out, retried) got us out of the state.
+#[derive(Component)]
+struct OfflineLoadingUi;
+
+#[derive(Component)]
+struct BackToMenuButton;
+
+/// Shown for the whole `LoadingOffline` state, not just after a cancel: the
+/// file input's `cancel` event isn't fired by every browser (e.g. Firefox),
+/// so a player who dismisses the dialog without choosing a file can otherwise
+/// be stuck on the picker with no event ever reaching `receive_offline_puzzle`.
+fn spawn_back_to_menu_button(mut commands: Commands) {
+    commands
+        .spawn((
+            OfflineLoadingUi,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(8.0),
+                    left: Val::Px(8.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    BackToMenuButton,
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(12.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section("Back to menu", TextStyle::default()));
+                });
+        });
+}
+
+fn despawn_offline_loading_ui(mut commands: Commands, ui_root: Query<Entity, With<OfflineLoadingUi>>) {
+    for entity in &ui_root {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn handle_back_to_menu_button(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    interactions: Query<&Interaction, (With<BackToMenuButton>, Changed<Interaction>)>,
+) {
+    for interaction in &interactions {
+        if *interaction == Interaction::Pressed {
+            commands.remove_resource::<ImagePicker>();
+            commands.remove_resource::<OfflineMode>();
+            next_state.set(AppState::MainMenu);
+            break;
+        }
+    }
+}
+
+fn spawn_image_picker_task(mut commands: Commands, mut loading_msg: ResMut<LoadingMessage>) {
+    loading_msg.0 = String::from("Choose an image to turn into a puzzle");
+    spawn_picker(&mut commands);
+}
+
+/// The picker has to open here, not inside the async task: browsers require
+/// `.click()` on a file input to run synchronously from user activation, or
+/// they silently refuse to show the dialog.
+fn spawn_picker(commands: &mut Commands) {
+    let Some(chosen_rx) = open_file_picker() else {
+        return;
+    };
+    let thread_pool = AsyncComputeTaskPool::get();
+    let picker = ImagePicker::spawn(thread_pool, move |_client_rx, client_tx| async move {
+        if let Some(image) = decode_picked_file(chosen_rx).await {
+            let _ = client_tx.send(image);
+        }
+    });
+    commands.insert_resource(picker);
+}
+
+fn receive_offline_puzzle(
+    mut commands: Commands,
+    mut picker: ResMut<ImagePicker>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut loading_msg: ResMut<LoadingMessage>,
+) {
+    match picker.output.try_recv() {
+        Ok(image) => {
+            commands.insert_resource(Puzzle::new(image, OFFLINE_NUM_ROWS, OFFLINE_NUM_COLS));
+            commands.remove_resource::<ImagePicker>();
+            next_state.set(AppState::Cutting);
+        }
+        Err(TryRecvError::Empty) => (),
+        Err(TryRecvError::Disconnected) => {
+            // The user dismissed the dialog, or the chosen file wasn't a
+            // decodable image. Reopening the picker here would try to
+            // `.click()` it outside of user activation and silently no-op
+            // (see `spawn_picker`), so show a real button instead.
+            commands.remove_resource::<ImagePicker>();
+            loading_msg.0 = String::from("No image selected");
+            spawn_retry_ui(&mut commands);
+        }
+    }
+}
+
+#[derive(Component)]
+struct RetryUi;
+
+#[derive(Component)]
+struct RetryPickerButton;
+
+fn spawn_retry_ui(commands: &mut Commands) {
+    commands
+        .spawn((
+            RetryUi,
+            OfflineLoadingUi,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("No image was selected.", TextStyle::default()));
+            parent
+                .spawn((
+                    RetryPickerButton,
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(12.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section("Choose an image", TextStyle::default()));
+                });
+        });
+}
+
+fn handle_retry_button(
+    mut commands: Commands,
+    mut loading_msg: ResMut<LoadingMessage>,
+    interactions: Query<&Interaction, (With<RetryPickerButton>, Changed<Interaction>)>,
+    retry_ui: Query<Entity, With<RetryUi>>,
+) {
+    for interaction in &interactions {
+        if *interaction == Interaction::Pressed {
+            for entity in &retry_ui {
+                commands.entity(entity).despawn_recursive();
+            }
+            loading_msg.0 = String::from("Choose an image to turn into a puzzle");
+            spawn_picker(&mut commands);
+            break;
+        }
+    }
+}
+
+fn open_file_picker() -> Option<oneshot::Receiver<Option<web_sys::File>>> {
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let input = document
+        .create_element("input")
+        .ok()?
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .ok()?;
+    input.set_type("file");
+    input.set_accept("image/*");
+
+    let (chosen_tx, chosen_rx) = oneshot::channel();
+    let chosen_tx = Rc::new(RefCell::new(Some(chosen_tx)));
+
+    let input_for_change = input.clone();
+    let tx_for_change = chosen_tx.clone();
+    let on_change = Closure::wrap(Box::new(move || {
+        let file = input_for_change.files().and_then(|files| files.get(0));
+        if let Some(tx) = tx_for_change.borrow_mut().take() {
+            let _ = tx.send(file);
+        }
+    }) as Box<dyn FnMut()>);
+
+    let tx_for_cancel = chosen_tx.clone();
+    let on_cancel = Closure::wrap(Box::new(move || {
+        if let Some(tx) = tx_for_cancel.borrow_mut().take() {
+            let _ = tx.send(None);
+        }
+    }) as Box<dyn FnMut()>);
+
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    input.set_oncancel(Some(on_cancel.as_ref().unchecked_ref()));
+    on_change.forget();
+    on_cancel.forget();
+    input.click();
+
+    Some(chosen_rx)
+}
+
+async fn decode_picked_file(chosen_rx: oneshot::Receiver<Option<web_sys::File>>) -> Option<RgbaImage> {
+    let file = chosen_rx.await.ok()??;
+    let array_buffer = JsFuture::from(file.array_buffer()).await.ok()?;
+    let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
+    image::load_from_memory(&bytes).ok().map(|image| image.to_rgba8())
+}
+
+fn loopback_event_io(mut params: EventIoParams, mut puzzle: ResMut<Puzzle>) {
+    let outgoing = collect_outgoing_events(&mut params);
+
+    let mut new_events = Vec::new();
+    for event in outgoing {
+        new_events.extend(puzzle.apply_event(event));
+    }
+
+    dispatch_and_clear(&mut params, new_events);
+}
@@ -22,7 +22,9 @@ impl Plugin for PiecePlugin {
             .add_systems(Update, cut_pieces.run_if(in_state(AppState::Cutting)))
             .add_systems(
                 Update,
-                (move_piece, sort_pieces).run_if(in_state(AppState::Playing)),
+                (move_piece, sort_pieces).run_if(
+                    in_state(AppState::Playing).or_else(in_state(AppState::Reconnecting)),
+                ),
             );
     }
 }
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+/// The overall phase of a client session, driving which systems run via
+/// `OnEnter`/`OnExit`/`run_if(in_state(...))` across the other plugins.
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppState {
+    /// Landing screen: join online, play offline, or pick an image source.
+    /// See `menu::MainMenuPlugin`.
+    #[default]
+    MainMenu,
+    Connecting,
+    Downloading,
+    Cutting,
+    Playing,
+    /// The socket dropped mid-session; re-opening it and resyncing from a
+    /// server snapshot instead of re-cutting. See `network::resync_after_reconnect`.
+    Reconnecting,
+    /// A single-player session with no server: the user is picking an image
+    /// for `offline::receive_offline_puzzle` to build a `Puzzle` from.
+    LoadingOffline,
+    /// The player is choosing a curated remote image to start a puzzle from.
+    /// See `image_source::ImageSourcePickerPlugin`.
+    ImageSourcePicker,
+}